@@ -0,0 +1,403 @@
+//! Async driver for the Waveshare 7.5" E-Ink Display (HD), built on
+//! `embedded-hal-async`.
+//!
+//! Mirrors the blocking [`super::EPD7in5`] driver, but every step that would
+//! otherwise busy-poll the BUSY pin or block the executor on an SPI
+//! transfer instead awaits it. This matters most on this panel: a full
+//! refresh of the 7.5" HD takes several seconds, which would otherwise
+//! stall the whole executor on embassy/ESP32 style setups.
+//!
+//! Unlike the blocking driver, `SPI` here is an
+//! [`embedded_hal_async::spi::SpiDevice`], which asserts/deasserts chip
+//! select itself around each transfer, so there is no separate `CS` type
+//! parameter.
+//!
+//! This module is gated behind the `async` feature. This source tree is a
+//! snapshot with no `Cargo.toml` of its own to wire the feature into, so the
+//! manifest side of this can't be committed here; when folding this module
+//! into a real checkout, `Cargo.toml` needs:
+//!
+//! ```toml
+//! [features]
+//! async = ["dep:embedded-hal-async"]
+//!
+//! [dependencies]
+//! embedded-hal-async = { version = "1.0", optional = true }
+//! ```
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::{delay::DelayNs, digital::Wait, spi::SpiDevice};
+
+use crate::color::Color;
+use crate::traits::RefreshLUT;
+
+use super::command::Command;
+use super::{DEFAULT_BACKGROUND_COLOR, QUICK_REFRESHES_BEFORE_FULL};
+
+const IS_BUSY_LOW: bool = false;
+
+/// Async mirror of [`crate::traits::InternalWiAdditions`].
+pub trait InternalWiAdditionsAsync<SPI, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    BUSY: Wait + InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Initialise the controller, awaiting BUSY instead of polling it.
+    async fn init<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), SPI::Error>;
+}
+
+/// Async mirror of [`crate::traits::WaveshareDisplay`].
+pub trait WaveshareDisplayAsync<SPI, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    BUSY: Wait + InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// The color type used by this display
+    type DisplayColor;
+
+    /// Creates a new driver, running `init` on construction.
+    async fn new<DELAY: DelayNs>(
+        spi: SPI,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, SPI::Error>
+    where
+        Self: Sized;
+
+    /// Wakes the display up from [sleep](Self::sleep)
+    async fn wake_up<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), SPI::Error>;
+
+    /// Puts the display to sleep, setting it to a low-power mode.
+    async fn sleep(&mut self) -> Result<(), SPI::Error>;
+
+    /// Transmits a full frame to the SRAM of the EPD
+    async fn update_frame(&mut self, buffer: &[u8]) -> Result<(), SPI::Error>;
+
+    /// Transmits a frame to a sub-window of the SRAM of the EPD
+    #[allow(clippy::too_many_arguments)]
+    async fn update_partial_frame(
+        &mut self,
+        buffer: &[u8],
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), SPI::Error>;
+
+    /// Displays the frame that was last transmitted via `update_frame`
+    async fn display_frame(&mut self) -> Result<(), SPI::Error>;
+
+    /// Transmits and displays a full frame in one go
+    async fn update_and_display_frame(&mut self, buffer: &[u8]) -> Result<(), SPI::Error>;
+
+    /// Clears the frame buffer on the EPD with the declared background color
+    async fn clear_frame(&mut self) -> Result<(), SPI::Error>;
+
+    /// Sets the background color for the display
+    fn set_background_color(&mut self, color: Color);
+
+    /// Get the current background color
+    fn background_color(&self) -> &Color;
+
+    /// Get the width of the display
+    fn width(&self) -> u32;
+
+    /// Get the height of the display
+    fn height(&self) -> u32;
+
+    /// Selects the refresh LUT, see [RefreshLUT]
+    async fn set_lut(&mut self, refresh_rate: Option<RefreshLUT>) -> Result<(), SPI::Error>;
+
+    /// Whether the display is currently busy
+    async fn is_busy(&mut self) -> bool;
+}
+
+/// EPD7in5 (HD) async driver.
+pub struct EPD7in5Async<SPI, BUSY, DC, RST> {
+    spi: SPI,
+    busy: BUSY,
+    dc: DC,
+    rst: RST,
+    /// Background Color
+    color: Color,
+    /// Currently selected refresh mode, see [RefreshLUT]
+    refresh_lut: RefreshLUT,
+    /// Quick refreshes performed since the last full refresh
+    quick_refresh_count: u32,
+}
+
+impl<SPI, BUSY, DC, RST> EPD7in5Async<SPI, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    BUSY: Wait + InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    async fn command(&mut self, command: Command) -> Result<(), SPI::Error> {
+        self.dc.set_low().ok();
+        self.spi.write(&[command.address()]).await
+    }
+
+    async fn send_data(&mut self, data: &[u8]) -> Result<(), SPI::Error> {
+        self.dc.set_high().ok();
+        self.spi.write(data).await
+    }
+
+    async fn cmd_with_data(&mut self, command: Command, data: &[u8]) -> Result<(), SPI::Error> {
+        self.command(command).await?;
+        self.send_data(data).await
+    }
+
+    async fn wait_until_idle(&mut self) {
+        if IS_BUSY_LOW {
+            self.busy.wait_for_high().await.ok();
+        } else {
+            self.busy.wait_for_low().await.ok();
+        }
+    }
+
+    /// Number of quick (differential) refreshes performed since the last
+    /// full refresh. Mirrors [`super::EPD7in5::quick_refresh_count`].
+    pub fn quick_refresh_count(&self) -> u32 {
+        self.quick_refresh_count
+    }
+}
+
+impl<SPI, BUSY, DC, RST> InternalWiAdditionsAsync<SPI, BUSY, DC, RST>
+    for EPD7in5Async<SPI, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    BUSY: Wait + InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    async fn init<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.rst.set_low().ok();
+        delay.delay_ms(10).await;
+        self.rst.set_high().ok();
+        delay.delay_ms(10).await;
+
+        self.wait_until_idle().await;
+        self.command(Command::SW_RESET).await?;
+        self.wait_until_idle().await;
+
+        self.cmd_with_data(Command::AUTO_WRITE_RED, &[0xF7]).await?;
+        self.wait_until_idle().await;
+        self.cmd_with_data(Command::AUTO_WRITE_BW, &[0xF7]).await?;
+        self.wait_until_idle().await;
+
+        self.cmd_with_data(Command::SOFT_START, &[0xAE, 0xC7, 0xC3, 0xC0, 0x40])
+            .await?;
+
+        self.cmd_with_data(Command::DRIVER_OUTPUT_CONTROL, &[0xAF, 0x02, 0x01])
+            .await?;
+
+        self.cmd_with_data(Command::DATA_ENTRY, &[0x01]).await?;
+
+        self.cmd_with_data(Command::SET_RAM_X_START_END, &[0x00, 0x00, 0x6F, 0x03])
+            .await?;
+        self.cmd_with_data(Command::SET_RAM_Y_START_END, &[0xAF, 0x02, 0x00, 0x00])
+            .await?;
+
+        self.cmd_with_data(Command::VBD_CONTROL, &[0x05]).await?;
+
+        self.cmd_with_data(Command::TEMPERATURE_SENSOR_CONTROL, &[0x80])
+            .await?;
+
+        self.cmd_with_data(Command::DISPLAY_UPDATE_CONTROL_2, &[0xB1])
+            .await?;
+
+        self.command(Command::MASTER_ACTIVATION).await?;
+        self.wait_until_idle().await;
+
+        self.cmd_with_data(Command::SET_RAM_X_AC, &[0x00, 0x00]).await?;
+        self.cmd_with_data(Command::SET_RAM_Y_AC, &[0x00, 0x00]).await?;
+
+        Ok(())
+    }
+}
+
+impl<SPI, BUSY, DC, RST> WaveshareDisplayAsync<SPI, BUSY, DC, RST>
+    for EPD7in5Async<SPI, BUSY, DC, RST>
+where
+    SPI: SpiDevice,
+    BUSY: Wait + InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    type DisplayColor = Color;
+
+    async fn new<DELAY: DelayNs>(
+        spi: SPI,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        delay: &mut DELAY,
+    ) -> Result<Self, SPI::Error> {
+        let mut epd = EPD7in5Async {
+            spi,
+            busy,
+            dc,
+            rst,
+            color: DEFAULT_BACKGROUND_COLOR,
+            refresh_lut: RefreshLUT::Full,
+            quick_refresh_count: 0,
+        };
+
+        epd.init(delay).await?;
+
+        Ok(epd)
+    }
+
+    async fn wake_up<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.init(delay).await
+    }
+
+    async fn sleep(&mut self) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await;
+        self.cmd_with_data(Command::DEEP_SLEEP, &[0x01]).await?;
+        Ok(())
+    }
+
+    async fn update_frame(&mut self, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await;
+        self.cmd_with_data(Command::SET_RAM_Y_AC, &[0x00, 0x00]).await?;
+        self.cmd_with_data(Command::WRITE_RAM_BW, buffer).await?;
+        Ok(())
+    }
+
+    async fn update_partial_frame(
+        &mut self,
+        buffer: &[u8],
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), SPI::Error> {
+        let win = super::PartialWindow::new(x, y, width, height);
+
+        self.wait_until_idle().await;
+
+        self.cmd_with_data(Command::DATA_ENTRY, &[0x03]).await?;
+
+        self.cmd_with_data(Command::SET_RAM_X_START_END, &win.x_start_end_bytes())
+            .await?;
+        self.cmd_with_data(Command::SET_RAM_Y_START_END, &win.y_start_end_bytes())
+            .await?;
+
+        self.cmd_with_data(Command::SET_RAM_X_AC, &win.x_ac_bytes())
+            .await?;
+        self.cmd_with_data(Command::SET_RAM_Y_AC, &win.y_ac_bytes())
+            .await?;
+
+        self.command(Command::WRITE_RAM_BW).await?;
+        for row in 0..height as usize {
+            self.send_data(win.row(buffer, row)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn display_frame(&mut self) -> Result<(), SPI::Error> {
+        let force_full = self.quick_refresh_count >= QUICK_REFRESHES_BEFORE_FULL;
+
+        match self.refresh_lut {
+            RefreshLUT::Full => {
+                self.cmd_with_data(Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])
+                    .await?;
+                self.quick_refresh_count = 0;
+            }
+            RefreshLUT::Quick if force_full => {
+                self.cmd_with_data(Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])
+                    .await?;
+                self.quick_refresh_count = 0;
+            }
+            RefreshLUT::Quick => {
+                self.cmd_with_data(Command::DISPLAY_UPDATE_CONTROL_2, &[0xFF])
+                    .await?;
+                self.quick_refresh_count += 1;
+            }
+        }
+
+        self.command(Command::MASTER_ACTIVATION).await?;
+        self.wait_until_idle().await;
+        Ok(())
+    }
+
+    async fn update_and_display_frame(&mut self, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.update_frame(buffer).await?;
+        self.display_frame().await?;
+        Ok(())
+    }
+
+    async fn clear_frame(&mut self) -> Result<(), SPI::Error> {
+        let pixel_count = (super::WIDTH * super::HEIGHT / 8) as usize;
+
+        self.wait_until_idle().await;
+        self.cmd_with_data(Command::SET_RAM_Y_AC, &[0x00, 0x00]).await?;
+
+        for cmd in &[Command::WRITE_RAM_BW, Command::WRITE_RAM_RED] {
+            self.command(*cmd).await?;
+            let chunk = [0xFFu8; 64];
+            let mut remaining = pixel_count;
+            while remaining > 0 {
+                let n = remaining.min(chunk.len());
+                self.send_data(&chunk[..n]).await?;
+                remaining -= n;
+            }
+        }
+
+        self.cmd_with_data(Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])
+            .await?;
+        self.command(Command::MASTER_ACTIVATION).await?;
+        self.wait_until_idle().await;
+        self.quick_refresh_count = 0;
+        Ok(())
+    }
+
+    fn set_background_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    fn background_color(&self) -> &Color {
+        &self.color
+    }
+
+    fn width(&self) -> u32 {
+        super::WIDTH
+    }
+
+    fn height(&self) -> u32 {
+        super::HEIGHT
+    }
+
+    async fn set_lut(&mut self, refresh_rate: Option<RefreshLUT>) -> Result<(), SPI::Error> {
+        if let Some(refresh_rate) = refresh_rate {
+            // Mirrors super::EPD7in5::set_lut: only a genuine mode change
+            // resets the quick-refresh count.
+            let mode_changed = !matches!(
+                (&refresh_rate, &self.refresh_lut),
+                (RefreshLUT::Full, RefreshLUT::Full) | (RefreshLUT::Quick, RefreshLUT::Quick)
+            );
+            self.refresh_lut = refresh_rate;
+            if mode_changed {
+                self.quick_refresh_count = 0;
+            }
+        }
+        Ok(())
+    }
+
+    async fn is_busy(&mut self) -> bool {
+        if IS_BUSY_LOW {
+            matches!(self.busy.is_low(), Ok(true))
+        } else {
+            matches!(self.busy.is_high(), Ok(true))
+        }
+    }
+}
@@ -5,6 +5,8 @@
 //! - [Datasheet](https://www.waveshare.com/w/upload/2/27/7inch_HD_e-Paper_Specification.pdf)
 //! - [Waveshare Python driver](https://github.com/waveshare/e-Paper/blob/master/RaspberryPi_JetsonNano/python/lib/waveshare_epd/epd7in5_HD.py)
 //!
+use core::marker::PhantomData;
+
 use embedded_hal::{
     blocking::{delay::*, spi::Write},
     digital::v2::{InputPin, OutputPin},
@@ -22,6 +24,11 @@ mod graphics;
 #[cfg(feature = "graphics")]
 pub use self::graphics::Display7in5;
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+#[cfg(feature = "async")]
+pub use self::asynchronous::EPD7in5Async;
+
 /// Width of the display
 pub const WIDTH: u32 = 880;
 /// Height of the display
@@ -29,24 +36,220 @@ pub const HEIGHT: u32 = 528;
 /// Default Background Color
 pub const DEFAULT_BACKGROUND_COLOR: Color = Color::Black; // Inverted for HD (0xFF = White)
 const IS_BUSY_LOW: bool = false;
+/// Number of quick (differential) refreshes allowed before a full flashing
+/// refresh is forced automatically, to clear ghosting accumulated by the
+/// RED RAM bank staying untouched across quick refreshes.
+const QUICK_REFRESHES_BEFORE_FULL: u32 = 10;
+
+/// Window math for [`WaveshareDisplay::update_partial_frame`], shared by the
+/// blocking driver in this module and the async driver in
+/// [`asynchronous`](self::asynchronous), so the two don't drift out of sync.
+///
+/// `x` and `width` must already be multiples of 8 (the panel packs 8
+/// horizontal pixels per byte, and `buffer` holds exactly `width/8` bytes
+/// per row with no spare bits to pad a misaligned window with), and
+/// `height` must be non-zero. [`new`](Self::new) enforces all three
+/// unconditionally rather than only in debug builds: a caller-triggerable
+/// contract violation here silently programs a corrupted RAM window on the
+/// panel instead of panicking, which is worse than panicking in release too.
+pub(crate) struct PartialWindow {
+    pub(crate) x_start_byte: u8,
+    pub(crate) x_end_byte: u8,
+    pub(crate) y_start: u32,
+    pub(crate) y_end: u32,
+    src_stride: usize,
+    window_bytes: usize,
+}
+
+impl PartialWindow {
+    pub(crate) fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        assert_eq!(x % 8, 0, "update_partial_frame: x must be a multiple of 8");
+        assert_eq!(width % 8, 0, "update_partial_frame: width must be a multiple of 8");
+        assert_ne!(height, 0, "update_partial_frame: height must be non-zero");
+
+        let x_start = x - (x % 8);
+        let x_end = ((x + width + 7) / 8) * 8 - 1;
+
+        let x_start_byte = (x_start >> 3) as u8;
+        let x_end_byte = (x_end >> 3) as u8;
+
+        PartialWindow {
+            x_start_byte,
+            x_end_byte,
+            y_start: y,
+            y_end: y + height - 1,
+            src_stride: (width / 8) as usize,
+            window_bytes: (x_end_byte - x_start_byte + 1) as usize,
+        }
+    }
+
+    pub(crate) fn x_start_end_bytes(&self) -> [u8; 4] {
+        [self.x_start_byte, 0x00, self.x_end_byte, 0x00]
+    }
+
+    pub(crate) fn y_start_end_bytes(&self) -> [u8; 4] {
+        [
+            (self.y_start & 0xFF) as u8,
+            (self.y_start >> 8) as u8,
+            (self.y_end & 0xFF) as u8,
+            (self.y_end >> 8) as u8,
+        ]
+    }
+
+    pub(crate) fn x_ac_bytes(&self) -> [u8; 2] {
+        [self.x_start_byte, 0x00]
+    }
+
+    pub(crate) fn y_ac_bytes(&self) -> [u8; 2] {
+        [(self.y_start & 0xFF) as u8, (self.y_start >> 8) as u8]
+    }
+
+    /// The bytes of `buffer` for one row of the window. `new`'s
+    /// preconditions guarantee `window_bytes == src_stride`, so this is
+    /// always the full row.
+    pub(crate) fn row<'b>(&self, buffer: &'b [u8], row: usize) -> &'b [u8] {
+        let row_start = row * self.src_stride;
+        let row_end = row_start + self.window_bytes;
+        &buffer[row_start..row_end]
+    }
+}
+
+/// Decouples the driver logic below from a concrete SPI/GPIO type by
+/// routing it through this trait instead. Modeled after the interface
+/// abstraction used by the `ili9341` crate.
+///
+/// In practice every real driving path is still through [`WaveshareDisplay`],
+/// whose methods are SPI-shaped (`spi: &mut SPI` on every call) and so
+/// require `Bus = SPI`; [`EPD7in5`]'s own `command`/`send_data`/... methods
+/// are private and carry the same `Bus = SPI` bound. What this buys today is
+/// unit-testability: a mock `CommandInterface` lets the command sequence be
+/// asserted without a real SPI peripheral (see the `tests` module below), in
+/// the same spirit as `ili9341`'s interface trait even though it isn't yet
+/// exposed as a public non-SPI entry point.
+pub trait CommandInterface {
+    /// The per-call handle this interface needs, e.g. the shared SPI
+    /// peripheral for an SPI-backed interface.
+    type Bus;
+    /// Error type surfaced by bus operations
+    type Error;
+
+    /// Sends a command
+    fn command(&mut self, bus: &mut Self::Bus, command: Command) -> Result<(), Self::Error>;
+    /// Sends data
+    fn send_data(&mut self, bus: &mut Self::Bus, data: &[u8]) -> Result<(), Self::Error>;
+    /// Sends a command followed by its data
+    fn cmd_with_data(
+        &mut self,
+        bus: &mut Self::Bus,
+        command: Command,
+        data: &[u8],
+    ) -> Result<(), Self::Error>;
+    /// Sends the same data byte `count` times
+    fn data_x_times(&mut self, bus: &mut Self::Bus, value: u8, count: u32)
+        -> Result<(), Self::Error>;
+    /// Blocks until the BUSY line reports the controller is idle
+    fn wait_until_idle(&mut self, is_busy_low: bool);
+    /// Reports whether the controller is currently busy
+    fn is_busy(&self, is_busy_low: bool) -> bool;
+    /// Resets the controller by toggling RST
+    fn reset<DELAY: DelayMs<u8>>(&mut self, delay: &mut DELAY, duration_ms: u8);
+}
+
+impl<SPI, CS, BUSY, DC, RST> CommandInterface for DisplayInterface<SPI, CS, BUSY, DC, RST>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    type Bus = SPI;
+    type Error = SPI::Error;
+
+    fn command(&mut self, spi: &mut SPI, command: Command) -> Result<(), SPI::Error> {
+        self.cmd(spi, command)
+    }
+
+    fn send_data(&mut self, spi: &mut SPI, data: &[u8]) -> Result<(), SPI::Error> {
+        self.data(spi, data)
+    }
+
+    fn cmd_with_data(
+        &mut self,
+        spi: &mut SPI,
+        command: Command,
+        data: &[u8],
+    ) -> Result<(), SPI::Error> {
+        DisplayInterface::cmd_with_data(self, spi, command, data)
+    }
+
+    fn data_x_times(&mut self, spi: &mut SPI, value: u8, count: u32) -> Result<(), SPI::Error> {
+        DisplayInterface::data_x_times(self, spi, value, count)
+    }
+
+    fn wait_until_idle(&mut self, is_busy_low: bool) {
+        DisplayInterface::wait_until_idle(self, is_busy_low)
+    }
+
+    fn is_busy(&self, is_busy_low: bool) -> bool {
+        DisplayInterface::is_busy(self, is_busy_low)
+    }
+
+    fn reset<DELAY: DelayMs<u8>>(&mut self, delay: &mut DELAY, duration_ms: u8) {
+        DisplayInterface::reset(self, delay, duration_ms)
+    }
+}
+
+/// Builds the default SPI-backed [DisplayInterface] from the four control
+/// pins. [EPD7in5::new] constructs its `DI` through `From<(CS, BUSY, DC,
+/// RST)>`, so this impl is what keeps the default working without callers
+/// having to construct it by hand; any other `DI` used with `new` needs its
+/// own such impl.
+impl<SPI, CS, BUSY, DC, RST> From<(CS, BUSY, DC, RST)> for DisplayInterface<SPI, CS, BUSY, DC, RST>
+where
+    CS: OutputPin,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    fn from((cs, busy, dc, rst): (CS, BUSY, DC, RST)) -> Self {
+        DisplayInterface::new(cs, busy, dc, rst)
+    }
+}
 
 /// EPD7in5 (HD) driver
 ///
-pub struct EPD7in5<SPI, CS, BUSY, DC, RST> {
+/// Generic over the [CommandInterface] implementor `DI`, which defaults to
+/// the SPI-backed [DisplayInterface] so existing callers are unaffected.
+/// [`WaveshareDisplay`] itself is SPI-shaped (every method threads `spi: &mut
+/// SPI` through, so one physical SPI peripheral can be shared across
+/// devices), so driving a display through that trait still requires
+/// `DI::Bus = SPI`. There is no public entry point that drives `EPD7in5`
+/// with a non-`SPI` `Bus` today; what the `DI` parameter buys right now is
+/// that a mock implementor can stand in for `DI` in tests, exercising the
+/// command sequence without a real SPI peripheral.
+pub struct EPD7in5<SPI, CS, BUSY, DC, RST, DI = DisplayInterface<SPI, CS, BUSY, DC, RST>> {
     /// Connection Interface
-    interface: DisplayInterface<SPI, CS, BUSY, DC, RST>,
+    interface: DI,
     /// Background Color
     color: Color,
+    /// Currently selected refresh mode, see [RefreshLUT]
+    refresh_lut: RefreshLUT,
+    /// Quick refreshes performed since the last full refresh
+    quick_refresh_count: u32,
+    _spi: PhantomData<SPI>,
+    _pins: PhantomData<(CS, BUSY, DC, RST)>,
 }
 
-impl<SPI, CS, BUSY, DC, RST> InternalWiAdditions<SPI, CS, BUSY, DC, RST>
-    for EPD7in5<SPI, CS, BUSY, DC, RST>
+impl<SPI, CS, BUSY, DC, RST, DI> InternalWiAdditions<SPI, CS, BUSY, DC, RST>
+    for EPD7in5<SPI, CS, BUSY, DC, RST, DI>
 where
     SPI: Write<u8>,
     CS: OutputPin,
     BUSY: InputPin,
     DC: OutputPin,
     RST: OutputPin,
+    DI: CommandInterface<Bus = SPI, Error = SPI::Error>,
 {
     fn init<DELAY: DelayMs<u8>>(
         &mut self,
@@ -95,14 +298,15 @@ where
     }
 }
 
-impl<SPI, CS, BUSY, DC, RST> WaveshareDisplay<SPI, CS, BUSY, DC, RST>
-    for EPD7in5<SPI, CS, BUSY, DC, RST>
+impl<SPI, CS, BUSY, DC, RST, DI> WaveshareDisplay<SPI, CS, BUSY, DC, RST>
+    for EPD7in5<SPI, CS, BUSY, DC, RST, DI>
 where
     SPI: Write<u8>,
     CS: OutputPin,
     BUSY: InputPin,
     DC: OutputPin,
     RST: OutputPin,
+    DI: CommandInterface<Bus = SPI, Error = SPI::Error> + From<(CS, BUSY, DC, RST)>,
 {
     type DisplayColor = Color;
     fn new<DELAY: DelayMs<u8>>(
@@ -113,10 +317,17 @@ where
         rst: RST,
         delay: &mut DELAY,
     ) -> Result<Self, SPI::Error> {
-        let interface = DisplayInterface::new(cs, busy, dc, rst);
+        let interface = DI::from((cs, busy, dc, rst));
         let color = DEFAULT_BACKGROUND_COLOR;
 
-        let mut epd = EPD7in5 { interface, color };
+        let mut epd = EPD7in5 {
+            interface,
+            color,
+            refresh_lut: RefreshLUT::Full,
+            quick_refresh_count: 0,
+            _spi: PhantomData,
+            _pins: PhantomData,
+        };
 
         epd.init(spi, delay)?;
 
@@ -141,23 +352,62 @@ where
         self.wait_until_idle();
         self.cmd_with_data(spi, Command::SET_RAM_Y_AC, &[0x00, 0x00])?;
         self.cmd_with_data(spi, Command::WRITE_RAM_BW, buffer)?;
-        self.cmd_with_data(spi, Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])?;
+        // The RED RAM bank is intentionally left untouched here: the SSD1677
+        // keeps the previously displayed image in it, which is what makes a
+        // flash-free differential refresh possible in `display_frame`.
         Ok(())
     }
 
     fn update_partial_frame(
         &mut self,
-        _spi: &mut SPI,
-        _buffer: &[u8],
-        _x: u32,
-        _y: u32,
-        _width: u32,
-        _height: u32,
+        spi: &mut SPI,
+        buffer: &[u8],
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
     ) -> Result<(), SPI::Error> {
-        unimplemented!();
+        let win = PartialWindow::new(x, y, width, height);
+
+        self.wait_until_idle();
+
+        self.cmd_with_data(spi, Command::DATA_ENTRY, &[0x03])?;
+
+        self.cmd_with_data(spi, Command::SET_RAM_X_START_END, &win.x_start_end_bytes())?;
+        self.cmd_with_data(spi, Command::SET_RAM_Y_START_END, &win.y_start_end_bytes())?;
+
+        self.cmd_with_data(spi, Command::SET_RAM_X_AC, &win.x_ac_bytes())?;
+        self.cmd_with_data(spi, Command::SET_RAM_Y_AC, &win.y_ac_bytes())?;
+
+        self.command(spi, Command::WRITE_RAM_BW)?;
+        for row in 0..height as usize {
+            self.send_data(spi, win.row(buffer, row))?;
+        }
+
+        Ok(())
     }
 
     fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        // A full refresh is also forced every `QUICK_REFRESHES_BEFORE_FULL`
+        // quick refreshes, since the quick sequence never powers down the
+        // analog front end and ghosting otherwise accumulates over time.
+        let force_full = self.quick_refresh_count >= QUICK_REFRESHES_BEFORE_FULL;
+
+        match self.refresh_lut {
+            RefreshLUT::Full => {
+                self.cmd_with_data(spi, Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])?;
+                self.quick_refresh_count = 0;
+            }
+            RefreshLUT::Quick if force_full => {
+                self.cmd_with_data(spi, Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])?;
+                self.quick_refresh_count = 0;
+            }
+            RefreshLUT::Quick => {
+                self.cmd_with_data(spi, Command::DISPLAY_UPDATE_CONTROL_2, &[0xFF])?;
+                self.quick_refresh_count += 1;
+            }
+        }
+
         self.command(spi, Command::MASTER_ACTIVATION)?;
         self.wait_until_idle();
         Ok(())
@@ -186,6 +436,7 @@ where
         self.cmd_with_data(spi, Command::DISPLAY_UPDATE_CONTROL_2, &[0xF7])?;
         self.command(spi, Command::MASTER_ACTIVATION)?;
         self.wait_until_idle();
+        self.quick_refresh_count = 0;
         Ok(())
     }
 
@@ -208,9 +459,23 @@ where
     fn set_lut(
         &mut self,
         _spi: &mut SPI,
-        _refresh_rate: Option<RefreshLUT>,
+        refresh_rate: Option<RefreshLUT>,
     ) -> Result<(), SPI::Error> {
-        unimplemented!();
+        if let Some(refresh_rate) = refresh_rate {
+            // Only a genuine mode change should reset the quick-refresh
+            // count: re-selecting the same RefreshLUT before every update
+            // (a normal defensive pattern) must not keep the count from
+            // ever reaching QUICK_REFRESHES_BEFORE_FULL.
+            let mode_changed = !matches!(
+                (&refresh_rate, &self.refresh_lut),
+                (RefreshLUT::Full, RefreshLUT::Full) | (RefreshLUT::Quick, RefreshLUT::Quick)
+            );
+            self.refresh_lut = refresh_rate;
+            if mode_changed {
+                self.quick_refresh_count = 0;
+            }
+        }
+        Ok(())
     }
 
     fn is_busy(&self) -> bool {
@@ -218,20 +483,21 @@ where
     }
 }
 
-impl<SPI, CS, BUSY, DC, RST> EPD7in5<SPI, CS, BUSY, DC, RST>
+impl<SPI, CS, BUSY, DC, RST, DI> EPD7in5<SPI, CS, BUSY, DC, RST, DI>
 where
     SPI: Write<u8>,
     CS: OutputPin,
     BUSY: InputPin,
     DC: OutputPin,
     RST: OutputPin,
+    DI: CommandInterface<Bus = SPI, Error = SPI::Error>,
 {
     fn command(&mut self, spi: &mut SPI, command: Command) -> Result<(), SPI::Error> {
-        self.interface.cmd(spi, command)
+        self.interface.command(spi, command)
     }
 
     fn send_data(&mut self, spi: &mut SPI, data: &[u8]) -> Result<(), SPI::Error> {
-        self.interface.data(spi, data)
+        self.interface.send_data(spi, data)
     }
 
     fn cmd_with_data(
@@ -247,6 +513,14 @@ where
         self.interface.wait_until_idle(IS_BUSY_LOW)
     }
 
+    /// Number of quick (differential) refreshes performed since the last
+    /// full refresh. Useful for callers that want to decide for themselves
+    /// when to fall back to [RefreshLUT::Full] instead of relying on the
+    /// automatic `QUICK_REFRESHES_BEFORE_FULL` threshold.
+    pub fn quick_refresh_count(&self) -> u32 {
+        self.quick_refresh_count
+    }
+
     // fn send_resolution(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
     //     unimplemented!();
     //     // let w = self.width();
@@ -265,6 +539,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use core::convert::Infallible;
 
     #[test]
     fn epd_size() {
@@ -272,4 +547,210 @@ mod tests {
         assert_eq!(HEIGHT, 528);
         assert_eq!(DEFAULT_BACKGROUND_COLOR, Color::Black);
     }
+
+    #[test]
+    fn partial_window_aligns_to_byte_boundaries() {
+        let win = PartialWindow::new(8, 2, 16, 3);
+        assert_eq!(win.x_start_end_bytes(), [1, 0x00, 2, 0x00]);
+        assert_eq!(win.y_start_end_bytes(), [2, 0x00, 4, 0x00]);
+        assert_eq!(win.x_ac_bytes(), [1, 0x00]);
+        assert_eq!(win.y_ac_bytes(), [2, 0x00]);
+
+        let buffer = [0xAAu8; 6]; // 2 bytes/row * 3 rows
+        assert_eq!(win.row(&buffer, 0), &buffer[0..2]);
+        assert_eq!(win.row(&buffer, 2), &buffer[4..6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "x must be a multiple of 8")]
+    fn partial_window_rejects_misaligned_x_unconditionally() {
+        PartialWindow::new(3, 0, 8, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "height must be non-zero")]
+    fn partial_window_rejects_zero_height() {
+        PartialWindow::new(0, 0, 8, 0);
+    }
+
+    /// No-op pin, just enough to satisfy the `OutputPin`/`InputPin` bounds
+    /// `WaveshareDisplay` carries on every call; [`MockInterface`] below
+    /// never touches a real pin.
+    struct DummyPin;
+
+    impl OutputPin for DummyPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    impl InputPin for DummyPin {
+        type Error = Infallible;
+        fn is_high(&self) -> Result<bool, Infallible> {
+            Ok(false)
+        }
+        fn is_low(&self) -> Result<bool, Infallible> {
+            Ok(true)
+        }
+    }
+
+    /// No-op bus, just enough to satisfy the `SPI: Write<u8>` bound;
+    /// [`MockInterface`] never forwards to it.
+    struct DummySpi;
+
+    impl Write<u8> for DummySpi {
+        type Error = Infallible;
+        fn write(&mut self, _words: &[u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    /// Minimal mock [`CommandInterface`] that records the opcode byte of
+    /// each command and the bytes of each data write, so `EPD7in5`'s
+    /// command sequencing can be asserted without a real SPI peripheral.
+    #[derive(Default)]
+    struct MockInterface {
+        log: Vec<u8>,
+    }
+
+    impl CommandInterface for MockInterface {
+        type Bus = DummySpi;
+        type Error = Infallible;
+
+        fn command(&mut self, _bus: &mut DummySpi, command: Command) -> Result<(), Infallible> {
+            self.log.push(command.address());
+            Ok(())
+        }
+
+        fn send_data(&mut self, _bus: &mut DummySpi, data: &[u8]) -> Result<(), Infallible> {
+            self.log.extend_from_slice(data);
+            Ok(())
+        }
+
+        fn cmd_with_data(
+            &mut self,
+            bus: &mut DummySpi,
+            command: Command,
+            data: &[u8],
+        ) -> Result<(), Infallible> {
+            self.command(bus, command)?;
+            self.send_data(bus, data)
+        }
+
+        fn data_x_times(
+            &mut self,
+            _bus: &mut DummySpi,
+            value: u8,
+            count: u32,
+        ) -> Result<(), Infallible> {
+            self.log.extend(core::iter::repeat(value).take(count as usize));
+            Ok(())
+        }
+
+        fn wait_until_idle(&mut self, _is_busy_low: bool) {}
+
+        fn is_busy(&self, _is_busy_low: bool) -> bool {
+            false
+        }
+
+        fn reset<DELAY: DelayMs<u8>>(&mut self, _delay: &mut DELAY, _duration_ms: u8) {}
+    }
+
+    type MockEpd = EPD7in5<DummySpi, DummyPin, DummyPin, DummyPin, DummyPin, MockInterface>;
+
+    fn mock_epd(refresh_lut: RefreshLUT, quick_refresh_count: u32) -> MockEpd {
+        EPD7in5 {
+            interface: MockInterface::default(),
+            color: DEFAULT_BACKGROUND_COLOR,
+            refresh_lut,
+            quick_refresh_count,
+            _spi: PhantomData,
+            _pins: PhantomData,
+        }
+    }
+
+    #[test]
+    fn update_partial_frame_sends_windowed_command_sequence() {
+        let mut epd = mock_epd(RefreshLUT::Full, 0);
+
+        let buffer = [0xAAu8; 2];
+        epd.update_partial_frame(&mut DummySpi, &buffer, 0, 0, 16, 1)
+            .unwrap();
+
+        assert_eq!(
+            epd.interface.log,
+            vec![
+                Command::DATA_ENTRY.address(),
+                0x03,
+                Command::SET_RAM_X_START_END.address(),
+                0x00,
+                0x00,
+                0x01,
+                0x00,
+                Command::SET_RAM_Y_START_END.address(),
+                0x00,
+                0x00,
+                0x00,
+                0x00,
+                Command::SET_RAM_X_AC.address(),
+                0x00,
+                0x00,
+                Command::SET_RAM_Y_AC.address(),
+                0x00,
+                0x00,
+                Command::WRITE_RAM_BW.address(),
+                0xAA,
+                0xAA,
+            ]
+        );
+    }
+
+    #[test]
+    fn display_frame_uses_the_quick_lut_below_the_force_full_threshold() {
+        let mut epd = mock_epd(RefreshLUT::Quick, QUICK_REFRESHES_BEFORE_FULL - 1);
+
+        epd.display_frame(&mut DummySpi).unwrap();
+
+        assert_eq!(
+            epd.interface.log,
+            vec![
+                Command::DISPLAY_UPDATE_CONTROL_2.address(),
+                0xFF,
+                Command::MASTER_ACTIVATION.address(),
+            ]
+        );
+        assert_eq!(epd.quick_refresh_count, QUICK_REFRESHES_BEFORE_FULL);
+    }
+
+    #[test]
+    fn display_frame_forces_a_full_refresh_at_the_threshold() {
+        let mut epd = mock_epd(RefreshLUT::Quick, QUICK_REFRESHES_BEFORE_FULL);
+
+        epd.display_frame(&mut DummySpi).unwrap();
+
+        assert_eq!(
+            epd.interface.log,
+            vec![
+                Command::DISPLAY_UPDATE_CONTROL_2.address(),
+                0xF7,
+                Command::MASTER_ACTIVATION.address(),
+            ]
+        );
+        assert_eq!(epd.quick_refresh_count, 0);
+    }
+
+    #[test]
+    fn set_lut_only_resets_the_quick_refresh_count_on_a_mode_change() {
+        let mut epd = mock_epd(RefreshLUT::Quick, 3);
+
+        epd.set_lut(&mut DummySpi, Some(RefreshLUT::Quick)).unwrap();
+        assert_eq!(epd.quick_refresh_count, 3);
+
+        epd.set_lut(&mut DummySpi, Some(RefreshLUT::Full)).unwrap();
+        assert_eq!(epd.quick_refresh_count, 0);
+    }
 }